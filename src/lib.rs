@@ -12,6 +12,18 @@
 //!
 //! This crate can also be used without the standard library (`#![no_std]`).
 //!
+//! ## Features
+//!
+//! * `std` - Adds integrations with the standard library, such as a non-consuming
+//!   `std::io::Read` adapter for `ExpSliceRB<u8>`.
+//! * `xrun-timestamps` - Records the time of the most recent underrun and overrun in
+//!   `XrunStats`. This implies `std`, and adds a clock read to every recorded xrun.
+//! * `cache-padded` - Adds the `CachePadded` wrapper, which aligns a value to the size of a
+//!   cache line. This is useful for keeping producer-owned and consumer-owned fields on
+//!   separate cache lines when wrapping `ExpSliceRB` in a concurrent ring buffer.
+//! * `bytemuck` - Adds `write_pod()` and `read_pod_into()` methods to `ExpSliceRB<u8>` for
+//!   writing and reading streams of plain-old-data types without manual byte-casting.
+//!
 //! ## Example
 //! ```rust
 //! use core::num::NonZeroUsize;
@@ -60,6 +72,9 @@
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::num::NonZeroUsize;
 
 use alloc::vec::Vec;
@@ -125,6 +140,11 @@ pub struct ExpSliceRB<T: Default + Clone + Copy> {
     buffer: SliceRB<T>,
     index: isize,
     data_len: usize,
+    growth_policy: GrowthPolicy,
+    max_capacity: Option<NonZeroUsize>,
+    capacity_alignment: NonZeroUsize,
+    init_memory: bool,
+    xrun_stats: XrunStats,
 }
 
 impl<T: Default + Clone + Copy> ExpSliceRB<T> {
@@ -138,6 +158,11 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
             buffer: SliceRB::from_vec(vec),
             index: 0,
             data_len: 0,
+            growth_policy: GrowthPolicy::Exact,
+            max_capacity: None,
+            capacity_alignment: NonZeroUsize::new(1).unwrap(),
+            init_memory: false,
+            xrun_stats: XrunStats::default(),
         }
     }
 
@@ -162,19 +187,43 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     ///
     /// * This will panic if `capacity > isize::MAX`.
     /// * This will panic if allocation fails due to being out of memory.
+    ///
     /// [`ExpSliceRB`]: struct.ExpSliceRB.html
     pub fn with_capacity(capacity: NonZeroUsize) -> Self {
-        // Safe because our algorithm ensures data will always be written to
-        // before being read.
-        let buffer = unsafe { SliceRB::new_uninit(capacity) };
+        ExpSliceRBBuilder::new(capacity).build()
+    }
 
-        Self {
-            buffer,
-            index: 0,
-            data_len: 0,
+    /// Sets the length of the internal buffer, initializing any new elements with the
+    /// default value if the buffer was configured to do so.
+    fn set_buffer_len(&mut self, len: NonZeroUsize) {
+        if self.init_memory {
+            self.buffer.set_len(len, T::default());
+        } else {
+            // Safe because algorithm ensures data will always be written to
+            // before being read.
+            unsafe {
+                self.buffer.set_len_uninit(len);
+            }
         }
     }
 
+    /// Returns the capacity the buffer should grow to in order to fit at least `required`
+    /// elements, according to the growth policy, capacity alignment, and maximum capacity.
+    fn grown_capacity(&self, required: usize) -> usize {
+        let prev_capacity = self.buffer.len().get();
+
+        let capacity = match self.growth_policy {
+            GrowthPolicy::Exact => required,
+            GrowthPolicy::Doubling => core::cmp::max(required, prev_capacity.saturating_mul(2)),
+        };
+        constrain_capacity(
+            capacity,
+            required,
+            self.capacity_alignment,
+            self.max_capacity,
+        )
+    }
+
     /// Reads the next chunk of existing data into the given slice. If the length of existing
     /// data in the buffer is less than the length of the slice, then only that amount of data
     /// will be copied into the front of the slice.
@@ -183,6 +232,10 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     /// the next call to `read_into()` will start copying from where the previous call left off.
     /// If you don't want this behavior, use the `peek_into()` method instead.
     ///
+    /// If less data was copied than the length of the slice, then this is counted as an
+    /// underrun in `xrun_stats()`. With the `xrun-timestamps` feature enabled, this also
+    /// reads the system clock.
+    ///
     /// This does not allocate any memory and is real-time safe.
     ///
     /// ## Returns
@@ -210,6 +263,10 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     /// assert_eq!(large_read_slice, [1u32, 2, 0, 1, 2, 5, 5, 5]);
     /// ```
     pub fn read_into(&mut self, mut slice: &mut [T]) -> usize {
+        if self.data_len < slice.len() {
+            self.xrun_stats.record_underrun();
+        }
+
         // No data in buffer.
         if self.data_len == 0 {
             return 0;
@@ -297,6 +354,76 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
         slice.len()
     }
 
+    /// Consumes the leading elements of existing data for as long as `predicate` returns
+    /// `true`, stopping at the first element for which it returns `false`.
+    ///
+    /// If `slice` is `Some`, then the consumed elements are also copied into the front of
+    /// that slice, and no more than `slice.len()` elements will be consumed. If `slice` is
+    /// `None`, then the elements are simply discarded.
+    ///
+    /// This is useful for things like skipping padding or consuming all events older than
+    /// a given timestamp.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// ## Returns
+    /// This returns the total amount of data that was consumed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(6).unwrap());
+    ///
+    /// buf.write(&[0u32, 0, 0, 1, 2, 3]);
+    ///
+    /// // Skip the leading zeros.
+    /// assert_eq!(buf.read_while(None, |x| *x == 0), 3);
+    /// assert_eq!(buf.len(), 3);
+    ///
+    /// let mut read_slice = [5u32; 4];
+    /// assert_eq!(buf.read_while(Some(&mut read_slice), |x| *x < 3), 2);
+    /// assert_eq!(read_slice, [1u32, 2, 5, 5]);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    pub fn read_while<F>(&mut self, slice: Option<&mut [T]>, mut predicate: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // No data in buffer.
+        if self.data_len == 0 {
+            return 0;
+        }
+
+        let max_len = match &slice {
+            Some(slice) => core::cmp::min(slice.len(), self.data_len),
+            None => self.data_len,
+        };
+
+        // Find how many leading elements satisfy the predicate.
+        let (first, second) = self.buffer.as_slices_len(self.index, max_len);
+        let amount = first
+            .iter()
+            .chain(second.iter())
+            .take_while(|x| predicate(x))
+            .count();
+
+        if amount == 0 {
+            return 0;
+        }
+
+        if let Some(slice) = slice {
+            // Copy the data.
+            self.buffer.read_into(&mut slice[0..amount], self.index);
+        }
+
+        // Advance the index.
+        self.index = self.buffer.constrain(self.index + amount as isize);
+        self.data_len -= amount;
+
+        amount
+    }
+
     /// Append additional data into the buffer to be read later. More memory may be allocated
     /// if the buffer is not large enough.
     ///
@@ -326,6 +453,7 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     /// # Panics
     ///
     /// * This will panic if `capacity > isize::MAX`.
+    /// * This will panic if the new capacity would exceed the maximum capacity of the buffer.
     /// * This will panic if allocation fails due to being out of memory.
     pub fn write(&mut self, slice: &[T]) {
         let new_len = self.data_len + slice.len();
@@ -342,9 +470,68 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
         self.data_len = new_len;
     }
 
+    /// Append the data in the given Vec into the buffer to be read later, taking ownership
+    /// of the Vec.
+    ///
+    /// If the buffer is empty and the length of the Vec is at least the current capacity of
+    /// the buffer, then the Vec is used as the new backing storage of the buffer instead of
+    /// copying its data, and the previous backing storage is dropped. The length of the Vec
+    /// must also be a multiple of the capacity alignment or equal to the maximum capacity of
+    /// the buffer. Otherwise this is equivalent to calling `write()` with the contents of the
+    /// Vec.
+    ///
+    /// This may allocate or deallocate memory and is ***not*** real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(4).unwrap());
+    ///
+    /// buf.write_vec(vec![0u32, 1, 2, 3, 4, 5]);
+    /// assert_eq!(buf.len(), 6);
+    /// assert_eq!(buf.capacity().get(), 6);
+    ///
+    /// let mut read_slice = [0u32; 6];
+    /// buf.read_into(&mut read_slice);
+    /// assert_eq!(read_slice, [0u32, 1, 2, 3, 4, 5]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * This will panic if `capacity > isize::MAX`.
+    /// * This will panic if the new capacity would exceed the maximum capacity of the buffer.
+    /// * This will panic if allocation fails due to being out of memory.
+    pub fn write_vec(&mut self, vec: Vec<T>) {
+        // The Vec can only be used as the backing storage if its length is a capacity the
+        // buffer could have grown to, i.e. it is aligned or equal to the maximum capacity.
+        let is_valid_capacity = match self.max_capacity {
+            Some(max_capacity) if vec.len() > max_capacity.get() => false,
+            Some(max_capacity) if vec.len() == max_capacity.get() => true,
+            _ => align_capacity(vec.len(), self.capacity_alignment) == vec.len(),
+        };
+
+        let can_swap =
+            self.data_len == 0 && vec.len() >= self.buffer.len().get() && is_valid_capacity;
+
+        if !can_swap {
+            self.write(&vec);
+            return;
+        }
+
+        let len = vec.len();
+
+        self.buffer = SliceRB::from_vec(vec);
+        self.index = 0;
+        self.data_len = len;
+    }
+
     /// Append additional data into the buffer to be read later. If the data cannot fit
     /// into the buffer, then no data is copied and and error is returned.
     ///
+    /// A failed write is counted as an overrun in `xrun_stats()`. With the `xrun-timestamps`
+    /// feature enabled, this also reads the system clock.
+    ///
     /// This does not allocate any memory and is real-time safe.
     ///
     /// # Example
@@ -359,10 +546,12 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     /// assert_eq!(buf.try_write(&data), Ok(()));
     /// assert_eq!(buf.try_write(&data), Err(()));
     /// ```
+    #[allow(clippy::result_unit_err)]
     pub fn try_write(&mut self, slice: &[T]) -> Result<(), ()> {
         let new_len = self.data_len + slice.len();
 
         if new_len > self.buffer.len().get() {
+            self.xrun_stats.record_overrun();
             return Err(());
         }
 
@@ -375,18 +564,266 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
         Ok(())
     }
 
+    /// Retains only the elements of existing data for which `predicate` returns `true`,
+    /// removing all others. This works the same as `VecDeque::retain()`.
+    ///
+    /// The order of the retained elements is preserved, and elements are only moved if an
+    /// element before them was removed.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(6).unwrap());
+    ///
+    /// buf.write(&[0u32, 1, 2, 3, 4, 5]);
+    /// buf.retain(|x| x % 2 == 0);
+    /// assert_eq!(buf.len(), 3);
+    ///
+    /// let mut read_slice = [0u32; 3];
+    /// buf.read_into(&mut read_slice);
+    /// assert_eq!(read_slice, [0u32, 2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut retained = 0;
+
+        for i in 0..self.data_len {
+            let value = *self.buffer.get(self.index + i as isize);
+
+            if predicate(&value) {
+                if retained != i {
+                    *self.buffer.get_mut(self.index + retained as isize) = value;
+                }
+                retained += 1;
+            }
+        }
+
+        self.data_len = retained;
+    }
+
+    /// Rotates the existing data in place such that the first `n` elements move to the end
+    /// while the remaining elements move to the front. This works the same as
+    /// `VecDeque::rotate_left()`.
+    ///
+    /// If the buffer is full, this only adjusts the read index. Otherwise, this copies at
+    /// most `min(n, self.len() - n)` elements when there is enough unused capacity to do
+    /// so.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(8).unwrap());
+    ///
+    /// buf.write(&[0u32, 1, 2, 3, 4]);
+    /// buf.rotate_left(2);
+    ///
+    /// let mut read_slice = [0u32; 5];
+    /// buf.peek_into(&mut read_slice);
+    /// assert_eq!(read_slice, [2u32, 3, 4, 0, 1]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `n > self.len()`.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.data_len);
+
+        let k = self.data_len - n;
+        if n <= k {
+            self.rotate_left_inner(n);
+        } else {
+            self.rotate_right_inner(k);
+        }
+    }
+
+    /// Rotates the existing data in place such that the last `n` elements move to the
+    /// front while the remaining elements move to the end. This works the same as
+    /// `VecDeque::rotate_right()`.
+    ///
+    /// If the buffer is full, this only adjusts the read index. Otherwise, this copies at
+    /// most `min(n, self.len() - n)` elements when there is enough unused capacity to do
+    /// so.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(8).unwrap());
+    ///
+    /// buf.write(&[0u32, 1, 2, 3, 4]);
+    /// buf.rotate_right(2);
+    ///
+    /// let mut read_slice = [0u32; 5];
+    /// buf.peek_into(&mut read_slice);
+    /// assert_eq!(read_slice, [3u32, 4, 0, 1, 2]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `n > self.len()`.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.data_len);
+
+        let k = self.data_len - n;
+        if n <= k {
+            self.rotate_right_inner(n);
+        } else {
+            self.rotate_left_inner(k);
+        }
+    }
+
+    fn rotate_left_inner(&mut self, n: usize) {
+        if self.data_len == self.buffer.len().get() {
+            self.index = self.buffer.constrain(self.index + n as isize);
+        } else if n <= self.data_left() {
+            // Copy the first `n` elements into the unused space after the end of the data.
+            self.copy_within_ring(self.index, self.index + self.data_len as isize, n);
+            self.index = self.buffer.constrain(self.index + n as isize);
+        } else {
+            self.make_contiguous().rotate_left(n);
+        }
+    }
+
+    fn rotate_right_inner(&mut self, n: usize) {
+        if self.data_len == self.buffer.len().get() {
+            self.index = self.buffer.constrain(self.index - n as isize);
+        } else if n <= self.data_left() {
+            // Copy the last `n` elements into the unused space before the start of the data.
+            self.copy_within_ring(
+                self.index + (self.data_len - n) as isize,
+                self.index - n as isize,
+                n,
+            );
+            self.index = self.buffer.constrain(self.index - n as isize);
+        } else {
+            self.make_contiguous().rotate_right(n);
+        }
+    }
+
+    /// Copies `len` elements starting at index `src` to index `dst`, wrapping around the
+    /// end of the buffer as needed. The two regions must not overlap.
+    fn copy_within_ring(&mut self, src: isize, dst: isize, len: usize) {
+        let capacity = self.buffer.len().get();
+        let mut src = self.buffer.constrain(src) as usize;
+        let mut dst = self.buffer.constrain(dst) as usize;
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let chunk_len = remaining.min(capacity - src).min(capacity - dst);
+
+            self.buffer
+                .raw_data_mut()
+                .copy_within(src..src + chunk_len, dst);
+
+            src = (src + chunk_len) % capacity;
+            dst = (dst + chunk_len) % capacity;
+            remaining -= chunk_len;
+        }
+    }
+
+    /// Reads the oldest `slice.len()` elements of existing data into the given slice while
+    /// appending the previous contents of the slice into the buffer, all in a single pass.
+    /// The length of existing data in the buffer stays the same.
+    ///
+    /// This is equivalent to calling `read_into()` with a copy of the slice and then
+    /// `write()` with the original contents of the slice, but with roughly half the memory
+    /// traffic. This makes constant-latency delay lines and feedback loops a single call.
+    ///
+    /// If the buffer contains less than `slice.len()` elements of existing data, then
+    /// nothing is copied and an error is returned.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// // A delay line with a latency of 4 samples.
+    /// let mut buf = ExpSliceRB::<f32>::with_capacity(NonZeroUsize::new(4).unwrap());
+    /// buf.write(&[0.0; 4]);
+    ///
+    /// let mut block = [1.0f32, 2.0, 3.0];
+    /// assert_eq!(buf.exchange(&mut block), Ok(()));
+    /// assert_eq!(block, [0.0f32, 0.0, 0.0]);
+    ///
+    /// let mut block = [4.0f32, 5.0, 6.0];
+    /// assert_eq!(buf.exchange(&mut block), Ok(()));
+    /// assert_eq!(block, [0.0f32, 1.0, 2.0]);
+    ///
+    /// assert_eq!(buf.exchange(&mut [0.0; 5]), Err(()));
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn exchange(&mut self, slice: &mut [T]) -> Result<(), ()> {
+        if slice.len() > self.data_len {
+            return Err(());
+        }
+
+        let capacity = self.buffer.len().get();
+        let mut read_i = self.index as usize;
+        let mut write_i = self.buffer.constrain(self.index + self.data_len as isize) as usize;
+        let mut done = 0;
+
+        // Every element in the buffer is read before it is overwritten, since the write
+        // position always trails the read position by the length of existing data.
+        while done < slice.len() {
+            let chunk_len = (slice.len() - done)
+                .min(capacity - read_i)
+                .min(capacity - write_i);
+
+            let raw_data = self.buffer.raw_data_mut();
+            let chunk = &mut slice[done..done + chunk_len];
+
+            if read_i == write_i {
+                // The buffer is full, so the elements can simply be swapped.
+                raw_data[read_i..read_i + chunk_len].swap_with_slice(chunk);
+            } else {
+                for (i, x) in chunk.iter_mut().enumerate() {
+                    let old = raw_data[read_i + i];
+                    raw_data[write_i + i] = *x;
+                    *x = old;
+                }
+            }
+
+            read_i = (read_i + chunk_len) % capacity;
+            write_i = (write_i + chunk_len) % capacity;
+            done += chunk_len;
+        }
+
+        self.index = read_i as isize;
+
+        Ok(())
+    }
+
     /// Reserves capacity for at least `additional` more elements to be inserted
     /// into the buffer.
     ///
-    /// Due to the algorithm, no data will actually be initialized. However, more memory
+    /// Due to the algorithm, no data will actually be initialized (unless the buffer was
+    /// built with `init_memory(true)`). However, more memory
     /// may need to be allocated.
     ///
+    /// The new capacity is determined by the [`GrowthPolicy`] and capacity alignment of the
+    /// buffer (see [`ExpSliceRBBuilder`]).
+    ///
     /// This may allocate new memory and is ***not*** real-time safe.
     ///
     /// # Panics
     ///
     /// * This will panic if `capacity > isize::MAX`.
+    /// * This will panic if the new capacity would exceed the maximum capacity of the buffer.
     /// * This will panic if allocation fails due to being out of memory.
+    ///
+    /// [`GrowthPolicy`]: enum.GrowthPolicy.html
+    /// [`ExpSliceRBBuilder`]: struct.ExpSliceRBBuilder.html
     pub fn reserve(&mut self, additional: usize) {
         if additional == 0 {
             return;
@@ -395,12 +832,10 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
         let data_end = self.index as usize + self.data_len;
         let prev_buffer_len = self.buffer.len().get();
 
-        // Safe because algorithm ensures data will always be written to
-        // before being read.
-        unsafe {
-            self.buffer
-                .set_len_uninit(NonZeroUsize::new(prev_buffer_len + additional).unwrap());
-        }
+        let new_buffer_len = self.grown_capacity(prev_buffer_len + additional);
+        let additional = new_buffer_len - prev_buffer_len;
+
+        self.set_buffer_len(NonZeroUsize::new(new_buffer_len).unwrap());
 
         if data_end > prev_buffer_len {
             // If the existing data wraps around, then copy the wrapped portion to make the
@@ -425,6 +860,140 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
         }
     }
 
+    /// Rearranges the internal memory of the buffer so that all existing data is stored
+    /// in one contiguous slice, which is then returned.
+    ///
+    /// This does not move any data if the existing data is already contiguous. Otherwise,
+    /// this attempts to use as little copying as possible.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(4).unwrap());
+    ///
+    /// buf.write(&[0u32, 1, 2]);
+    /// let mut read_slice = [0u32; 2];
+    /// buf.read_into(&mut read_slice);
+    /// buf.write(&[3u32, 4, 5]);
+    ///
+    /// assert_eq!(buf.make_contiguous(), &[2u32, 3, 4, 5]);
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let capacity = self.buffer.len().get();
+        let start = self.index as usize;
+
+        if start + self.data_len > capacity {
+            // The existing data wraps around.
+
+            let first_len = capacity - start;
+            let second_len = self.data_len - first_len;
+            let free_len = capacity - self.data_len;
+
+            let raw_data = self.buffer.raw_data_mut();
+
+            if free_len >= first_len {
+                // Shift the second portion to the right and copy the first portion in
+                // front of it.
+                raw_data.copy_within(0..second_len, first_len);
+                raw_data.copy_within(start..capacity, 0);
+
+                self.index = 0;
+            } else if free_len >= second_len {
+                // Shift the first portion to the left and copy the second portion after it.
+                raw_data.copy_within(start..capacity, second_len);
+                raw_data.copy_within(0..second_len, self.data_len);
+
+                self.index = second_len as isize;
+            } else {
+                // Move the second portion to directly in front of the first portion, and
+                // then rotate the two portions into the correct order.
+                raw_data.copy_within(0..second_len, free_len);
+                raw_data[free_len..].rotate_left(second_len);
+
+                self.index = free_len as isize;
+            }
+        }
+
+        let start = self.index as usize;
+        &mut self.buffer.raw_data_mut()[start..start + self.data_len]
+    }
+
+    /// Returns a contiguous slice of `len` unused elements directly after the end of the
+    /// existing data, reserving more capacity or moving existing data if needed.
+    ///
+    /// The returned elements are set to the default value.
+    fn vacant_slice_mut(&mut self, len: usize) -> &mut [T] {
+        loop {
+            let capacity = self.buffer.len().get();
+            let start = self.index as usize;
+            let data_end = start + self.data_len;
+
+            // If the data ends at or wraps around the end of the buffer, then the free space
+            // starts at the beginning of the buffer and is already contiguous.
+            let contiguous_free_len = if data_end >= capacity {
+                capacity - self.data_len
+            } else {
+                capacity - data_end
+            };
+
+            if contiguous_free_len >= len {
+                break;
+            }
+
+            if data_end < capacity && self.data_left() >= len {
+                // Move the existing data to the front of the buffer so that the free space
+                // after it is contiguous.
+                self.buffer.raw_data_mut().copy_within(start..data_end, 0);
+                self.index = 0;
+            } else {
+                self.reserve(len - self.data_left());
+            }
+        }
+
+        let start = self.buffer.constrain(self.index + self.data_len as isize) as usize;
+        let slice = &mut self.buffer.raw_data_mut()[start..start + len];
+        slice.fill(T::default());
+        slice
+    }
+
+    /// Writes the default value into all of the unused allocated capacity of the buffer.
+    /// Existing data in the buffer is left untouched.
+    ///
+    /// Memory returned by the allocator is usually not backed by physical pages until it is
+    /// first written to, so the first writes into a freshly allocated or expanded buffer can
+    /// cause page faults. Calling this after `with_capacity()` or `reserve()` during setup
+    /// makes sure those page faults don't happen later on a real-time thread.
+    ///
+    /// This does not allocate any memory, but it does write to the entire unused capacity of
+    /// the buffer, so it is best called during setup and not in a real-time context.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<f32>::with_capacity(NonZeroUsize::new(1 << 16).unwrap());
+    /// buf.prefault();
+    ///
+    /// assert_eq!(buf.len(), 0);
+    /// assert_eq!(buf.capacity().get(), 1 << 16);
+    /// ```
+    pub fn prefault(&mut self) {
+        let data_left = self.data_left();
+        if data_left == 0 {
+            return;
+        }
+
+        let (first, second) = self
+            .buffer
+            .as_mut_slices_len(self.index + self.data_len as isize, data_left);
+
+        first.fill(T::default());
+        second.fill(T::default());
+    }
+
     /// Removes all existing data in the buffer.
     ///
     /// This does not allocate any memory and is real-time safe.
@@ -438,22 +1007,30 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     /// Removes all existing data in the buffer and sets the allocated capacity of the buffer. This will also call
     /// `Vec::shrink_to_fit()` on the internal Vec.
     ///
-    /// Due to the algorithm, no data will actually be initialized.
+    /// Due to the algorithm, no data will actually be initialized (unless the buffer was
+    /// built with `init_memory(true)`).
+    ///
+    /// The capacity is rounded up to a multiple of the capacity alignment of the buffer
+    /// (unless that would exceed the maximum capacity).
     ///
     /// This may allocate or deallocate memory and is ***not*** real-time safe.
     ///
     /// # Panics
     ///
     /// * This will panic if `capacity > isize::MAX`.
+    /// * This will panic if `capacity` exceeds the maximum capacity of the buffer.
     /// * This will panic if allocation fails due to being out of memory.
     pub fn clear_and_shrink_to_capacity(&mut self, capacity: NonZeroUsize) {
         self.clear();
 
-        // Safe because algorithm ensures data will always be written to
-        // before being read.
-        unsafe {
-            self.buffer.set_len_uninit(capacity);
-        }
+        let capacity = constrain_capacity(
+            capacity.get(),
+            capacity.get(),
+            self.capacity_alignment,
+            self.max_capacity,
+        );
+
+        self.set_buffer_len(NonZeroUsize::new(capacity).unwrap());
 
         self.buffer.shrink_to_fit();
     }
@@ -486,23 +1063,779 @@ impl<T: Default + Clone + Copy> ExpSliceRB<T> {
     pub fn is_empty(&self) -> bool {
         self.data_len == 0
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns the policy used to determine how much the buffer grows when more capacity
+    /// is needed.
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.growth_policy
+    }
 
-    #[test]
-    fn test() {
-        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
-        assert_eq!(buf.len(), 0);
-        assert_eq!(buf.capacity().get(), 4);
+    /// Returns the maximum capacity the buffer is allowed to grow to, if any.
+    pub fn max_capacity(&self) -> Option<NonZeroUsize> {
+        self.max_capacity
+    }
 
-        let data = [0u32, 1, 2, 3];
+    /// Returns the amount of underruns and overruns (xruns) that have occurred in this
+    /// buffer.
+    ///
+    /// An underrun is counted every time `read_into()` copies less data than the length of
+    /// the given slice, and an overrun is counted every time `try_write()` fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u32>::with_capacity(NonZeroUsize::new(4).unwrap());
+    ///
+    /// buf.write(&[0u32, 1, 2]);
+    /// let _ = buf.try_write(&[3u32, 4]);
+    ///
+    /// let mut read_slice = [0u32; 4];
+    /// buf.read_into(&mut read_slice);
+    ///
+    /// assert_eq!(buf.xrun_stats().underruns(), 1);
+    /// assert_eq!(buf.xrun_stats().overruns(), 1);
+    /// ```
+    pub fn xrun_stats(&self) -> XrunStats {
+        self.xrun_stats
+    }
 
-        buf.write(&data);
-        assert_eq!(buf.len(), 4);
-        assert_eq!(buf.buffer.raw_data(), data);
+    /// Resets the underrun and overrun counts back to zero.
+    pub fn reset_xrun_stats(&mut self) {
+        self.xrun_stats = XrunStats::default();
+    }
+}
+
+/// Statistics on the amount of underruns and overruns (xruns) that have occurred in an
+/// [`ExpSliceRB`].
+///
+/// [`ExpSliceRB`]: struct.ExpSliceRB.html
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct XrunStats {
+    underruns: u64,
+    overruns: u64,
+    #[cfg(feature = "xrun-timestamps")]
+    last_underrun: Option<std::time::Instant>,
+    #[cfg(feature = "xrun-timestamps")]
+    last_overrun: Option<std::time::Instant>,
+}
+
+impl XrunStats {
+    /// Returns the number of times `read_into()` copied less data than requested.
+    pub fn underruns(&self) -> u64 {
+        self.underruns
+    }
+
+    /// Returns the number of times `try_write()` failed because the data did not fit.
+    pub fn overruns(&self) -> u64 {
+        self.overruns
+    }
+
+    /// Returns the time of the most recent underrun, if any.
+    #[cfg(feature = "xrun-timestamps")]
+    pub fn last_underrun(&self) -> Option<std::time::Instant> {
+        self.last_underrun
+    }
+
+    /// Returns the time of the most recent overrun, if any.
+    #[cfg(feature = "xrun-timestamps")]
+    pub fn last_overrun(&self) -> Option<std::time::Instant> {
+        self.last_overrun
+    }
+
+    fn record_underrun(&mut self) {
+        self.underruns += 1;
+
+        #[cfg(feature = "xrun-timestamps")]
+        {
+            self.last_underrun = Some(std::time::Instant::now());
+        }
+    }
+
+    fn record_overrun(&mut self) {
+        self.overruns += 1;
+
+        #[cfg(feature = "xrun-timestamps")]
+        {
+            self.last_overrun = Some(std::time::Instant::now());
+        }
+    }
+}
+
+/// The policy used to determine how much an [`ExpSliceRB`] grows when more capacity is
+/// needed.
+///
+/// [`ExpSliceRB`]: struct.ExpSliceRB.html
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthPolicy {
+    /// Grow to exactly the capacity that is needed. This uses the least amount of memory,
+    /// but can cause frequent allocations if the buffer keeps growing by small amounts.
+    #[default]
+    Exact,
+    /// Grow to at least double the previous capacity. This keeps the amount of allocations
+    /// low if the buffer keeps growing by small amounts.
+    Doubling,
+}
+
+/// A builder used to configure and create an [`ExpSliceRB`].
+///
+/// # Example
+/// ```rust
+/// # use core::num::NonZeroUsize;
+/// # use expanding_slice_rb::{ExpSliceRB, ExpSliceRBBuilder, GrowthPolicy};
+/// let mut buf: ExpSliceRB<f32> = ExpSliceRBBuilder::new(NonZeroUsize::new(100).unwrap())
+///     .growth_policy(GrowthPolicy::Doubling)
+///     .max_capacity(NonZeroUsize::new(1024).unwrap())
+///     .capacity_alignment(NonZeroUsize::new(16).unwrap())
+///     .init_memory(true)
+///     .build();
+///
+/// assert_eq!(buf.capacity().get(), 112);
+///
+/// buf.write(&[0.0; 113]);
+/// assert_eq!(buf.capacity().get(), 224);
+/// ```
+///
+/// [`ExpSliceRB`]: struct.ExpSliceRB.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpSliceRBBuilder {
+    capacity: NonZeroUsize,
+    growth_policy: GrowthPolicy,
+    max_capacity: Option<NonZeroUsize>,
+    capacity_alignment: NonZeroUsize,
+    init_memory: bool,
+}
+
+impl ExpSliceRBBuilder {
+    /// Create a new builder with the given initial allocated capacity.
+    ///
+    /// If possible, it is a good idea to set `capacity` to the largest you expect the
+    /// buffer to get to avoid future memory allocations.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            growth_policy: GrowthPolicy::Exact,
+            max_capacity: None,
+            capacity_alignment: NonZeroUsize::new(1).unwrap(),
+            init_memory: false,
+        }
+    }
+
+    /// Set the initial allocated capacity of the buffer.
+    pub fn capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the policy used to determine how much the buffer grows when more capacity is
+    /// needed.
+    ///
+    /// By default this is set to `GrowthPolicy::Exact`.
+    pub fn growth_policy(mut self, growth_policy: GrowthPolicy) -> Self {
+        self.growth_policy = growth_policy;
+        self
+    }
+
+    /// Set the maximum capacity the buffer is allowed to grow to. Growing the buffer past
+    /// this capacity will cause a panic, so use `try_write()` when writing to a buffer near
+    /// its maximum capacity.
+    ///
+    /// By default there is no maximum capacity.
+    pub fn max_capacity(mut self, max_capacity: NonZeroUsize) -> Self {
+        self.max_capacity = Some(max_capacity);
+        self
+    }
+
+    /// Set the alignment of the capacity of the buffer, in elements. The capacity of the
+    /// buffer will always be rounded up to a multiple of this value (unless that would
+    /// exceed the maximum capacity). This is useful when data is processed in fixed-size
+    /// blocks.
+    ///
+    /// Note this aligns the *length* of the allocated buffer, not the address of its memory.
+    ///
+    /// By default this is set to `1`.
+    pub fn capacity_alignment(mut self, capacity_alignment: NonZeroUsize) -> Self {
+        self.capacity_alignment = capacity_alignment;
+        self
+    }
+
+    /// If `true`, then all allocated memory (including memory allocated when the buffer
+    /// grows) will be initialized with the default value. This is slower, but also means
+    /// the memory is touched up front instead of on first use.
+    ///
+    /// By default this is set to `false`.
+    pub fn init_memory(mut self, init_memory: bool) -> Self {
+        self.init_memory = init_memory;
+        self
+    }
+
+    /// Create a new empty [`ExpSliceRB`] with this configuration.
+    ///
+    /// This allocates new memory and is ***not*** real-time safe.
+    ///
+    /// # Panics
+    ///
+    /// * This will panic if `capacity > isize::MAX`.
+    /// * This will panic if `capacity` exceeds the maximum capacity.
+    /// * This will panic if allocation fails due to being out of memory.
+    ///
+    /// [`ExpSliceRB`]: struct.ExpSliceRB.html
+    pub fn build<T: Default + Clone + Copy>(self) -> ExpSliceRB<T> {
+        let capacity = constrain_capacity(
+            self.capacity.get(),
+            self.capacity.get(),
+            self.capacity_alignment,
+            self.max_capacity,
+        );
+        let capacity = NonZeroUsize::new(capacity).unwrap();
+
+        let buffer = if self.init_memory {
+            SliceRB::new(capacity, T::default())
+        } else {
+            // Safe because our algorithm ensures data will always be written to
+            // before being read.
+            unsafe { SliceRB::new_uninit(capacity) }
+        };
+
+        ExpSliceRB {
+            buffer,
+            index: 0,
+            data_len: 0,
+            growth_policy: self.growth_policy,
+            max_capacity: self.max_capacity,
+            capacity_alignment: self.capacity_alignment,
+            init_memory: self.init_memory,
+            xrun_stats: XrunStats::default(),
+        }
+    }
+}
+
+/// Rounds `capacity` up to the nearest multiple of `alignment` and then clamps it to
+/// `max_capacity`, so a maximum capacity which is not a multiple of the alignment is still
+/// allowed.
+///
+/// # Panics
+///
+/// This will panic if `required` exceeds `max_capacity`.
+fn constrain_capacity(
+    capacity: usize,
+    required: usize,
+    alignment: NonZeroUsize,
+    max_capacity: Option<NonZeroUsize>,
+) -> usize {
+    let capacity = align_capacity(capacity, alignment);
+
+    if let Some(max_capacity) = max_capacity {
+        assert!(
+            required <= max_capacity.get(),
+            "ExpSliceRB capacity of {} exceeds the maximum capacity of {}",
+            required,
+            max_capacity
+        );
+
+        core::cmp::min(capacity, max_capacity.get())
+    } else {
+        capacity
+    }
+}
+
+/// Rounds `capacity` up to the nearest multiple of `alignment`.
+fn align_capacity(capacity: usize, alignment: NonZeroUsize) -> usize {
+    let alignment = alignment.get();
+    let remainder = capacity % alignment;
+
+    if remainder == 0 {
+        capacity
+    } else {
+        capacity
+            .checked_add(alignment - remainder)
+            .expect("ExpSliceRB capacity overflow")
+    }
+}
+
+/// Repeatedly feeds blocks of `block_size` elements from `src` through the `process`
+/// closure, appending its output to `dst`. This is useful for processors whose input and
+/// output block sizes differ, such as resamplers and encoders.
+///
+/// For each block, `process` is given a slice of exactly `block_size` elements from the
+/// front of `src`, and a slice of `max_out_len` default-initialized elements directly after
+/// the existing data in `dst`. It returns the amount of output elements it actually wrote
+/// into the front of the output slice, and only those elements are appended to `dst`.
+///
+/// Blocks which wrap around the end of either buffer are made contiguous automatically.
+/// If less than `block_size` elements are left in `src`, they are left in the buffer to be
+/// completed by later writes.
+///
+/// This only allocates memory if `dst` does not have at least `max_out_len` elements of
+/// unused capacity. It is real-time safe if enough capacity was reserved in `dst`
+/// beforehand.
+///
+/// ## Returns
+/// This returns the total amount of blocks that were processed.
+///
+/// # Example
+/// ```rust
+/// # use core::num::NonZeroUsize;
+/// # use expanding_slice_rb::{pump, ExpSliceRB};
+/// let mut src = ExpSliceRB::<f32>::with_capacity(NonZeroUsize::new(8).unwrap());
+/// let mut dst = ExpSliceRB::<f32>::with_capacity(NonZeroUsize::new(16).unwrap());
+///
+/// src.write(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+///
+/// // Naive 2x upsampler processing two input samples at a time.
+/// let blocks = pump(
+///     &mut src,
+///     &mut dst,
+///     NonZeroUsize::new(2).unwrap(),
+///     4,
+///     |input, output| {
+///         for (i, x) in input.iter().enumerate() {
+///             output[i * 2] = *x;
+///             output[i * 2 + 1] = *x;
+///         }
+///         4
+///     },
+/// );
+///
+/// assert_eq!(blocks, 2);
+/// assert_eq!(src.len(), 1);
+/// assert_eq!(dst.len(), 8);
+/// ```
+///
+/// # Panics
+///
+/// * This will panic if `process` returns a value greater than `max_out_len`.
+/// * This will panic if `dst` would need to grow past its maximum capacity.
+/// * This will panic if allocation fails due to being out of memory.
+pub fn pump<T, U, F>(
+    src: &mut ExpSliceRB<T>,
+    dst: &mut ExpSliceRB<U>,
+    block_size: NonZeroUsize,
+    max_out_len: usize,
+    mut process: F,
+) -> usize
+where
+    T: Default + Clone + Copy,
+    U: Default + Clone + Copy,
+    F: FnMut(&[T], &mut [U]) -> usize,
+{
+    let block_size = block_size.get();
+    let mut blocks = 0;
+
+    while src.data_len >= block_size {
+        if src.index as usize + block_size > src.buffer.len().get() {
+            src.make_contiguous();
+        }
+
+        let start = src.index as usize;
+        let input = &src.buffer.raw_data()[start..start + block_size];
+
+        let output = dst.vacant_slice_mut(max_out_len);
+        let out_len = process(input, output);
+        assert!(out_len <= max_out_len);
+
+        dst.data_len += out_len;
+
+        // Advance the index.
+        src.index = src.buffer.constrain(src.index + block_size as isize);
+        src.data_len -= block_size;
+
+        blocks += 1;
+    }
+
+    blocks
+}
+
+/// Pads and aligns a value to the size of a cache line.
+///
+/// When building a concurrent ring buffer around an [`ExpSliceRB`], wrapping the
+/// producer-owned fields (such as the write index) and the consumer-owned fields (such as
+/// the read index) in separate `CachePadded`s places them on separate cache lines. This
+/// avoids false sharing, where the producer and consumer threads slow each other down by
+/// writing to the same cache line.
+///
+/// The alignment is 128 bytes on `x86_64`, `aarch64`, and `powerpc64` (where the hardware
+/// prefetches cache lines in pairs or uses 128 byte cache lines), and 64 bytes on all
+/// other architectures.
+///
+/// # Example
+/// ```rust
+/// # use expanding_slice_rb::CachePadded;
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+///
+/// struct Indices {
+///     write: CachePadded<AtomicUsize>,
+///     read: CachePadded<AtomicUsize>,
+/// }
+///
+/// let indices = Indices {
+///     write: CachePadded::new(AtomicUsize::new(0)),
+///     read: CachePadded::new(AtomicUsize::new(0)),
+/// };
+///
+/// indices.write.store(4, Ordering::Release);
+/// assert_eq!(indices.write.load(Ordering::Acquire), 4);
+/// assert!(core::mem::align_of::<CachePadded<AtomicUsize>>() >= 64);
+/// ```
+///
+/// [`ExpSliceRB`]: struct.ExpSliceRB.html
+#[cfg(feature = "cache-padded")]
+#[cfg_attr(
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64"
+    ),
+    repr(align(128))
+)]
+#[cfg_attr(
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64"
+    )),
+    repr(align(64))
+)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CachePadded<T> {
+    value: T,
+}
+
+#[cfg(feature = "cache-padded")]
+impl<T> CachePadded<T> {
+    /// Pads and aligns the given value to the size of a cache line.
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+#[cfg(feature = "cache-padded")]
+impl<T> core::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[cfg(feature = "cache-padded")]
+impl<T> core::ops::DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(feature = "cache-padded")]
+impl<T> From<T> for CachePadded<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A cursor used to keep track of the playback position when reading the existing data
+/// in an [`ExpSliceRB`] as a loop.
+///
+/// [`ExpSliceRB`]: struct.ExpSliceRB.html
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoopCursor {
+    pos: usize,
+    looped: bool,
+}
+
+impl LoopCursor {
+    /// Create a new cursor positioned at the start of the loop.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current position of the cursor relative to the start of the existing
+    /// data.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns `true` if the cursor has wrapped around the end of the loop at least once.
+    pub fn has_looped(&self) -> bool {
+        self.looped
+    }
+
+    /// Moves the cursor back to the start of the loop.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+macro_rules! impl_crossfaded_loop {
+    ($t:ty) => {
+        impl ExpSliceRB<$t> {
+            /// Reads the existing data as a loop into the given slice starting from the
+            /// position of `cursor`, wrapping around to the start of the data as many times
+            /// as needed to fill the slice. This is ***not*** streaming and does not effect
+            /// the length of existing data in the buffer.
+            ///
+            /// To avoid clicks at the loop point, the last `fade_len` elements of the
+            /// existing data are linearly crossfaded into the first `fade_len` elements
+            /// every time the loop wraps around, which shortens the loop by `fade_len`
+            /// elements. The first pass through the loop is not crossfaded. `fade_len` is
+            /// clamped to half the length of the existing data.
+            ///
+            /// This does not allocate any memory and is real-time safe.
+            ///
+            /// ## Returns
+            /// This returns the total amount of data that was copied into `slice`, which
+            /// is `0` if the buffer is empty and `slice.len()` otherwise.
+            ///
+            /// # Example
+            /// ```rust
+            /// # use core::num::NonZeroUsize;
+            /// # use expanding_slice_rb::{ExpSliceRB, LoopCursor};
+            #[doc = concat!("let mut buf = ExpSliceRB::<", stringify!($t), ">::with_capacity(NonZeroUsize::new(6).unwrap());")]
+            /// buf.write(&[0.0, 1.0, 2.0, 3.0, 4.0, 2.0]);
+            ///
+            /// let mut cursor = LoopCursor::new();
+            /// let mut read_slice = [0.0; 8];
+            /// assert_eq!(buf.peek_looped_crossfaded(&mut read_slice, &mut cursor, 2), 8);
+            /// assert_eq!(read_slice, [0.0, 1.0, 2.0, 3.0, 4.0, 1.5, 2.0, 3.0]);
+            /// assert_eq!(cursor.position(), 0);
+            /// assert_eq!(buf.len(), 6);
+            /// ```
+            pub fn peek_looped_crossfaded(
+                &self,
+                slice: &mut [$t],
+                cursor: &mut LoopCursor,
+                fade_len: usize,
+            ) -> usize {
+                // No data in buffer.
+                if self.data_len == 0 {
+                    return 0;
+                }
+
+                let fade_len = core::cmp::min(fade_len, self.data_len / 2);
+                let loop_len = self.data_len - fade_len;
+
+                if cursor.pos >= loop_len {
+                    cursor.pos %= loop_len;
+                    cursor.looped = true;
+                }
+
+                let mut done = 0;
+                while done < slice.len() {
+                    let remaining = slice.len() - done;
+
+                    let amount = if cursor.looped && cursor.pos < fade_len {
+                        // Crossfade the tail of the data into the start of the loop.
+                        let amount = core::cmp::min(fade_len - cursor.pos, remaining);
+
+                        for (i, x) in slice[done..done + amount].iter_mut().enumerate() {
+                            let pos = cursor.pos + i;
+                            let gain = pos as $t / fade_len as $t;
+
+                            let head = *self.buffer.get(self.index + pos as isize);
+                            let tail = *self.buffer.get(self.index + (loop_len + pos) as isize);
+
+                            *x = head * gain + tail * (1.0 - gain);
+                        }
+
+                        amount
+                    } else {
+                        let amount = core::cmp::min(loop_len - cursor.pos, remaining);
+
+                        self.buffer.read_into(
+                            &mut slice[done..done + amount],
+                            self.index + cursor.pos as isize,
+                        );
+
+                        amount
+                    };
+
+                    done += amount;
+                    cursor.pos += amount;
+
+                    if cursor.pos == loop_len {
+                        cursor.pos = 0;
+                        cursor.looped = true;
+                    }
+                }
+
+                slice.len()
+            }
+        }
+    };
+}
+
+impl_crossfaded_loop!(f32);
+impl_crossfaded_loop!(f64);
+
+#[cfg(feature = "bytemuck")]
+impl ExpSliceRB<u8> {
+    /// Append a slice of plain-old-data values into the buffer as raw bytes (in native
+    /// endianness) to be read later. More memory may be allocated if the buffer is not
+    /// large enough.
+    ///
+    /// This may allocate new memory and is ***not*** real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u8>::with_capacity(NonZeroUsize::new(16).unwrap());
+    ///
+    /// buf.write_pod(&[1.0f32, 2.0, 3.0]);
+    /// assert_eq!(buf.len(), 12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * This will panic if `capacity > isize::MAX`.
+    /// * This will panic if the new capacity would exceed the maximum capacity of the buffer.
+    /// * This will panic if allocation fails due to being out of memory.
+    pub fn write_pod<U: bytemuck::Pod>(&mut self, data: &[U]) {
+        self.write(bytemuck::cast_slice(data));
+    }
+
+    /// Reads the next chunk of existing bytes into the given slice of plain-old-data
+    /// values (in native endianness). Only whole values are read, so if the length of
+    /// existing data is not a multiple of `size_of::<U>()`, then the trailing bytes are left
+    /// in the buffer. If there are fewer whole values in the buffer than the length of the
+    /// slice, then only that amount of values will be copied into the front of the slice.
+    ///
+    /// This is streaming in the same way as `read_into()`, and also counts an underrun in
+    /// `xrun_stats()` if less values were copied than the length of the slice. With the
+    /// `xrun-timestamps` feature enabled, this also reads the system clock.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// ## Returns
+    /// This returns the total amount of values (not bytes) that were copied into `slice`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// let mut buf = ExpSliceRB::<u8>::with_capacity(NonZeroUsize::new(16).unwrap());
+    ///
+    /// buf.write_pod(&[1.0f32, 2.0, 3.0]);
+    /// buf.write(&[0u8, 0]);
+    ///
+    /// let mut read_slice = [0.0f32; 4];
+    /// assert_eq!(buf.read_pod_into(&mut read_slice), 3);
+    /// assert_eq!(read_slice, [1.0f32, 2.0, 3.0, 0.0]);
+    ///
+    /// // The incomplete trailing bytes are left in the buffer.
+    /// assert_eq!(buf.len(), 2);
+    /// ```
+    pub fn read_pod_into<U: bytemuck::Pod>(&mut self, slice: &mut [U]) -> usize {
+        let value_size = core::mem::size_of::<U>();
+        if value_size == 0 {
+            return 0;
+        }
+
+        let amount = core::cmp::min(self.data_len / value_size, slice.len());
+        if amount < slice.len() {
+            self.xrun_stats.record_underrun();
+        }
+
+        // Casting to bytes can never fail, since `u8` has an alignment of 1 and the
+        // length of the byte slice is always a multiple of `value_size`.
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut slice[0..amount]);
+        self.read_into(bytes) / value_size
+    }
+}
+
+#[cfg(feature = "std")]
+impl ExpSliceRB<u8> {
+    /// Returns a [`PeekReader`] which implements `std::io::Read` over the existing data in
+    /// the buffer without consuming it.
+    ///
+    /// This does not allocate any memory and is real-time safe.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use core::num::NonZeroUsize;
+    /// # use expanding_slice_rb::ExpSliceRB;
+    /// use std::io::Read;
+    ///
+    /// let mut buf = ExpSliceRB::<u8>::with_capacity(NonZeroUsize::new(8).unwrap());
+    /// buf.write(b"RIFFdata");
+    ///
+    /// let mut magic = [0u8; 4];
+    /// buf.peek_reader().read_exact(&mut magic).unwrap();
+    /// assert_eq!(&magic, b"RIFF");
+    ///
+    /// // The data is still in the buffer.
+    /// assert_eq!(buf.len(), 8);
+    /// ```
+    ///
+    /// [`PeekReader`]: struct.PeekReader.html
+    pub fn peek_reader(&self) -> PeekReader<'_> {
+        PeekReader { buf: self, pos: 0 }
+    }
+}
+
+/// An adapter implementing `std::io::Read` over the existing data in an [`ExpSliceRB`]
+/// without consuming it. This keeps track of its own read position, so `Read`-based
+/// probing can be done on the data while the real consumer still sees everything
+/// afterwards.
+///
+/// This is created with `ExpSliceRB::peek_reader()`.
+///
+/// [`ExpSliceRB`]: struct.ExpSliceRB.html
+#[cfg(feature = "std")]
+pub struct PeekReader<'a> {
+    buf: &'a ExpSliceRB<u8>,
+    pos: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a> PeekReader<'a> {
+    /// Returns the amount of bytes that have been read from this reader so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the amount of bytes that are left to be read from this reader.
+    pub fn remaining(&self) -> usize {
+        self.buf.data_len - self.pos
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::io::Read for PeekReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let amount = core::cmp::min(buf.len(), self.remaining());
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        self.buf
+            .buffer
+            .read_into(&mut buf[0..amount], self.buf.index + self.pos as isize);
+        self.pos += amount;
+
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
+        assert_eq!(buf.len(), 0);
+        assert_eq!(buf.capacity().get(), 4);
+
+        let data = [0u32, 1, 2, 3];
+
+        buf.write(&data);
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.buffer.raw_data(), data);
 
         let mut read = [0u32; 4];
 
@@ -583,4 +1916,511 @@ mod tests {
         assert_eq!(buf.capacity().get(), 5);
         assert!(buf.raw_capacity().get() >= 5);
     }
+
+    #[test]
+    fn test_read_while() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
+        assert_eq!(buf.read_while(None, |_| true), 0);
+
+        // Make the existing data wrap around.
+        buf.write(&[9, 9, 9]);
+        let mut read = [0u32; 3];
+        buf.read_into(&mut read);
+        buf.write(&[0, 0, 0, 1]);
+        assert_eq!(buf.buffer.raw_data(), [0, 0, 1, 0]);
+
+        let mut read = [5u32; 2];
+        assert_eq!(buf.read_while(Some(&mut read), |x| *x == 0), 2);
+        assert_eq!(read, [0, 0]);
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.index, 1);
+
+        assert_eq!(buf.read_while(None, |x| *x == 0), 1);
+        assert_eq!(buf.len(), 1);
+        assert_eq!(buf.read_while(None, |x| *x == 0), 0);
+        assert_eq!(buf.len(), 1);
+        assert_eq!(buf.read_while(None, |_| true), 1);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_prefault() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::from_vec(alloc::vec![9; 5]);
+
+        // Make the existing data wrap around.
+        buf.write(&[1, 2, 3, 4]);
+        let mut read = [0u32; 3];
+        buf.read_into(&mut read);
+        buf.write(&[5, 6]);
+        assert_eq!(buf.buffer.raw_data(), [6, 2, 3, 4, 5]);
+
+        buf.prefault();
+        assert_eq!(buf.buffer.raw_data(), [6, 0, 0, 4, 5]);
+        assert_eq!(buf.len(), 3);
+
+        buf.write(&[7, 8]);
+        buf.prefault();
+        assert_eq!(buf.buffer.raw_data(), [6, 7, 8, 4, 5]);
+
+        let mut read = [0u32; 5];
+        assert_eq!(buf.read_into(&mut read), 5);
+        assert_eq!(read, [4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRBBuilder::new(NonZeroUsize::new(3).unwrap())
+            .capacity_alignment(NonZeroUsize::new(4).unwrap())
+            .growth_policy(GrowthPolicy::Doubling)
+            .max_capacity(NonZeroUsize::new(12).unwrap())
+            .init_memory(true)
+            .build();
+        assert_eq!(buf.capacity().get(), 4);
+        assert_eq!(buf.buffer.raw_data(), [0, 0, 0, 0]);
+
+        buf.write(&[1, 2, 3]);
+        let mut read = [0u32; 2];
+        buf.read_into(&mut read);
+        buf.write(&[4, 5, 6, 7]);
+        assert_eq!(buf.capacity().get(), 8);
+
+        buf.write(&[8, 9, 10]);
+        assert_eq!(buf.capacity().get(), 8);
+
+        // Doubling is clamped to the maximum capacity.
+        buf.write(&[11]);
+        assert_eq!(buf.capacity().get(), 12);
+
+        let mut read = [0u32; 12];
+        assert_eq!(buf.read_into(&mut read), 9);
+        assert_eq!(read, [3, 4, 5, 6, 7, 8, 9, 10, 11, 0, 0, 0]);
+
+        buf.clear_and_shrink_to_capacity(NonZeroUsize::new(5).unwrap());
+        assert_eq!(buf.capacity().get(), 8);
+    }
+
+    #[test]
+    fn test_unaligned_max_capacity() {
+        let builder = ExpSliceRBBuilder::new(NonZeroUsize::new(4).unwrap())
+            .capacity_alignment(NonZeroUsize::new(4).unwrap())
+            .max_capacity(NonZeroUsize::new(10).unwrap());
+
+        let mut buf: ExpSliceRB<u32> = builder.build();
+        buf.write(&[0; 10]);
+        assert_eq!(buf.capacity().get(), 10);
+
+        buf.clear_and_shrink_to_capacity(NonZeroUsize::new(10).unwrap());
+        assert_eq!(buf.capacity().get(), 10);
+        buf.clear_and_shrink_to_capacity(NonZeroUsize::new(5).unwrap());
+        assert_eq!(buf.capacity().get(), 8);
+
+        let buf: ExpSliceRB<u32> = builder.capacity(NonZeroUsize::new(10).unwrap()).build();
+        assert_eq!(buf.capacity().get(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_capacity() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRBBuilder::new(NonZeroUsize::new(4).unwrap())
+            .max_capacity(NonZeroUsize::new(6).unwrap())
+            .build();
+
+        buf.write(&[0; 6]);
+        buf.write(&[0]);
+    }
+
+    #[test]
+    fn test_exchange() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(6).unwrap());
+        assert_eq!(buf.exchange(&mut []), Ok(()));
+        assert_eq!(buf.exchange(&mut [0]), Err(()));
+
+        // Make the existing data wrap around with a partially full buffer.
+        buf.write(&[0, 0, 0, 0]);
+        let mut read = [0u32; 3];
+        buf.read_into(&mut read);
+        buf.write(&[1, 2, 3]);
+        assert_eq!(buf.len(), 4);
+
+        let mut slice = [4, 5, 6, 7];
+        assert_eq!(buf.exchange(&mut slice), Ok(()));
+        assert_eq!(slice, [0, 1, 2, 3]);
+        assert_eq!(buf.len(), 4);
+
+        // Full buffer.
+        buf.write(&[8, 9]);
+        let mut slice = [10, 11, 12, 13, 14];
+        assert_eq!(buf.exchange(&mut slice), Ok(()));
+        assert_eq!(slice, [4, 5, 6, 7, 8]);
+
+        let mut slice = [0u32; 7];
+        assert_eq!(buf.exchange(&mut slice), Err(()));
+        assert_eq!(buf.read_into(&mut slice), 6);
+        assert_eq!(slice, [9, 10, 11, 12, 13, 14, 0]);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(6).unwrap());
+        buf.retain(|_| false);
+        assert_eq!(buf.len(), 0);
+
+        // Make the existing data wrap around.
+        buf.write(&[0, 0, 0, 0]);
+        let mut read = [0u32; 4];
+        buf.read_into(&mut read);
+        buf.write(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(buf.buffer.raw_data(), [3, 4, 5, 6, 1, 2]);
+
+        buf.retain(|x| *x != 2 && *x != 5);
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.buffer.raw_data(), [4, 6, 5, 6, 1, 3]);
+
+        buf.retain(|_| true);
+        assert_eq!(buf.len(), 4);
+
+        let mut read = [0u32; 6];
+        assert_eq!(buf.read_into(&mut read), 4);
+        assert_eq!(read, [1, 3, 4, 6, 0, 0]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        fn contents(buf: &mut ExpSliceRB<u32>) -> Vec<u32> {
+            let mut read = alloc::vec![0u32; buf.len()];
+            buf.peek_into(&mut read);
+            read
+        }
+
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(8).unwrap());
+        buf.rotate_left(0);
+        buf.rotate_right(0);
+
+        // Make the existing data wrap around.
+        buf.write(&[0; 6]);
+        let mut read = [0u32; 6];
+        buf.read_into(&mut read);
+        buf.write(&[0, 1, 2, 3, 4, 5]);
+
+        // Enough unused space for the copy.
+        buf.rotate_left(2);
+        assert_eq!(contents(&mut buf), [2, 3, 4, 5, 0, 1]);
+        buf.rotate_right(2);
+        assert_eq!(contents(&mut buf), [0, 1, 2, 3, 4, 5]);
+        buf.rotate_left(4);
+        assert_eq!(contents(&mut buf), [4, 5, 0, 1, 2, 3]);
+        buf.rotate_right(4);
+        assert_eq!(contents(&mut buf), [0, 1, 2, 3, 4, 5]);
+
+        // Not enough unused space for the copy.
+        buf.rotate_left(3);
+        assert_eq!(contents(&mut buf), [3, 4, 5, 0, 1, 2]);
+        buf.rotate_right(3);
+        assert_eq!(contents(&mut buf), [0, 1, 2, 3, 4, 5]);
+
+        // Full buffer.
+        buf.write(&[6, 7]);
+        let index = buf.index;
+        buf.rotate_left(3);
+        assert_eq!(contents(&mut buf), [3, 4, 5, 6, 7, 0, 1, 2]);
+        buf.rotate_right(5);
+        assert_eq!(contents(&mut buf), [6, 7, 0, 1, 2, 3, 4, 5]);
+        assert_eq!(buf.index, buf.buffer.constrain(index - 2));
+    }
+
+    #[test]
+    fn test_write_vec() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
+
+        // The Vec is too small to be swapped in.
+        buf.write_vec(alloc::vec![1, 2, 3]);
+        assert_eq!(buf.capacity().get(), 4);
+        let mut read = [0u32; 4];
+        assert_eq!(buf.read_into(&mut read), 3);
+        assert_eq!(read, [1, 2, 3, 0]);
+
+        let vec = alloc::vec![4, 5, 6, 7, 8];
+        let ptr = vec.as_ptr();
+        buf.write_vec(vec);
+        assert_eq!(buf.buffer.raw_data().as_ptr(), ptr);
+        assert_eq!(buf.capacity().get(), 5);
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf.index, 0);
+
+        // The buffer is not empty.
+        buf.write_vec(alloc::vec![9; 5]);
+        assert_eq!(buf.len(), 10);
+        assert_eq!(buf.capacity().get(), 10);
+        let mut read = [0u32; 10];
+        assert_eq!(buf.read_into(&mut read), 10);
+        assert_eq!(read, [4, 5, 6, 7, 8, 9, 9, 9, 9, 9]);
+
+        buf.write_vec(Vec::new());
+        assert!(buf.is_empty());
+
+        // A Vec with a length equal to an unaligned maximum capacity can be swapped in.
+        let mut buf: ExpSliceRB<u32> = ExpSliceRBBuilder::new(NonZeroUsize::new(4).unwrap())
+            .capacity_alignment(NonZeroUsize::new(4).unwrap())
+            .max_capacity(NonZeroUsize::new(10).unwrap())
+            .build();
+        let vec = alloc::vec![1; 10];
+        let ptr = vec.as_ptr();
+        buf.write_vec(vec);
+        assert_eq!(buf.buffer.raw_data().as_ptr(), ptr);
+        assert_eq!(buf.capacity().get(), 10);
+
+        // An unaligned Vec is copied instead.
+        buf.clear_and_shrink_to_capacity(NonZeroUsize::new(4).unwrap());
+        let vec = alloc::vec![2; 5];
+        let ptr = vec.as_ptr();
+        buf.write_vec(vec);
+        assert_ne!(buf.buffer.raw_data().as_ptr(), ptr);
+        assert_eq!(buf.capacity().get(), 8);
+        assert_eq!(buf.len(), 5);
+    }
+
+    #[test]
+    fn test_xrun_stats() {
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
+        assert_eq!(buf.xrun_stats(), XrunStats::default());
+
+        let mut read = [0u32; 2];
+        assert_eq!(buf.read_into(&mut []), 0);
+        assert_eq!(buf.xrun_stats().underruns(), 0);
+        assert_eq!(buf.read_into(&mut read), 0);
+        assert_eq!(buf.xrun_stats().underruns(), 1);
+
+        buf.write(&[0, 1, 2]);
+        assert_eq!(buf.try_write(&[3]), Ok(()));
+        assert_eq!(buf.try_write(&[4]), Err(()));
+        assert_eq!(buf.xrun_stats().overruns(), 1);
+
+        assert_eq!(buf.read_into(&mut read), 2);
+        assert_eq!(buf.read_into(&mut read), 2);
+        assert_eq!(buf.xrun_stats().underruns(), 1);
+        assert_eq!(buf.read_into(&mut read), 0);
+        assert_eq!(buf.xrun_stats().underruns(), 2);
+
+        #[cfg(feature = "xrun-timestamps")]
+        {
+            assert!(buf.xrun_stats().last_underrun().is_some());
+            assert!(buf.xrun_stats().last_overrun().is_some());
+        }
+
+        buf.reset_xrun_stats();
+        assert_eq!(buf.xrun_stats(), XrunStats::default());
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        // Enough free space for the first portion.
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::from_vec(alloc::vec![0; 8]);
+        buf.index = 7;
+        buf.write(&[1, 2, 3]);
+        assert_eq!(buf.make_contiguous(), [1, 2, 3]);
+        assert_eq!(buf.index, 0);
+
+        // Enough free space for the second portion.
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::from_vec(alloc::vec![0; 8]);
+        buf.index = 4;
+        buf.write(&[1, 2, 3, 4, 5]);
+        assert_eq!(buf.make_contiguous(), [1, 2, 3, 4, 5]);
+        assert_eq!(buf.index, 1);
+
+        // Not enough free space for either portion.
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::from_vec(alloc::vec![0; 8]);
+        buf.index = 4;
+        buf.write(&[1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(buf.make_contiguous(), [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(buf.index, 1);
+
+        let mut buf: ExpSliceRB<u32> = ExpSliceRB::from_vec(alloc::vec![0; 8]);
+        buf.index = 3;
+        buf.write(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(buf.make_contiguous(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(buf.index, 0);
+
+        let mut read = [0u32; 8];
+        assert_eq!(buf.read_into(&mut read), 8);
+        assert_eq!(read, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_pump() {
+        let mut src: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(5).unwrap());
+        let mut dst: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
+
+        // Sum each block of 3 elements into 1 element.
+        let mut sum = |input: &[u32], output: &mut [u32]| {
+            output[0] = input.iter().sum();
+            1
+        };
+        let block_size = NonZeroUsize::new(3).unwrap();
+
+        src.write(&[1, 2, 3, 4]);
+        assert_eq!(pump(&mut src, &mut dst, block_size, 1, &mut sum), 1);
+        assert_eq!(src.len(), 1);
+
+        // The next block wraps around the end of `src`.
+        src.write(&[5, 6, 7, 8]);
+        assert_eq!(pump(&mut src, &mut dst, block_size, 1, &mut sum), 1);
+        assert_eq!(src.len(), 2);
+        assert_eq!(src.capacity().get(), 5);
+
+        // Make the unused space in `dst` wrap around.
+        let mut read = [0u32; 1];
+        dst.read_into(&mut read);
+        assert_eq!(read, [6]);
+        dst.write(&[0, 0]);
+        dst.read_into(&mut read);
+
+        src.write(&[9, 10, 11, 12]);
+        assert_eq!(pump(&mut src, &mut dst, block_size, 1, &mut sum), 2);
+        assert_eq!(src.len(), 0);
+
+        // Nothing was produced.
+        src.write(&[1, 1, 1]);
+        assert_eq!(pump(&mut src, &mut dst, block_size, 1, |_, _| 0), 1);
+
+        let mut read = [0u32; 5];
+        assert_eq!(dst.read_into(&mut read), 4);
+        assert_eq!(read, [0, 0, 24, 33, 0]);
+
+        // The existing data in `dst` ends exactly at the end of the buffer, so the unused
+        // space at the start of the buffer is used without moving any data.
+        let mut dst: ExpSliceRB<u32> = ExpSliceRB::with_capacity(NonZeroUsize::new(8).unwrap());
+        dst.write(&[0; 4]);
+        let mut read = [0u32; 4];
+        dst.read_into(&mut read);
+        dst.write(&[1, 2, 3, 4]);
+        assert_eq!(dst.index, 4);
+
+        src.clear();
+        src.write(&[5, 6, 7]);
+        assert_eq!(pump(&mut src, &mut dst, block_size, 2, &mut sum), 1);
+        assert_eq!(dst.index, 4);
+        assert_eq!(dst.len(), 5);
+        assert_eq!(dst.buffer.raw_data()[0], 18);
+
+        let mut read = [0u32; 5];
+        assert_eq!(dst.read_into(&mut read), 5);
+        assert_eq!(read, [1, 2, 3, 4, 18]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_peek_reader() {
+        use std::io::Read;
+
+        let mut buf: ExpSliceRB<u8> = ExpSliceRB::with_capacity(NonZeroUsize::new(4).unwrap());
+
+        // Make the existing data wrap around.
+        buf.write(&[0, 0, 0]);
+        let mut read = [0u8; 3];
+        buf.read_into(&mut read);
+        buf.write(&[1, 2, 3, 4]);
+
+        let mut reader = buf.peek_reader();
+        let mut read = [0u8; 3];
+        assert_eq!(reader.read(&mut read).unwrap(), 3);
+        assert_eq!(read, [1, 2, 3]);
+        assert_eq!(reader.position(), 3);
+        assert_eq!(reader.read(&mut read).unwrap(), 1);
+        assert_eq!(read, [4, 2, 3]);
+        assert_eq!(reader.remaining(), 0);
+        assert_eq!(reader.read(&mut read).unwrap(), 0);
+
+        let mut all = std::vec::Vec::new();
+        buf.peek_reader().read_to_end(&mut all).unwrap();
+        assert_eq!(all, [1, 2, 3, 4]);
+        assert_eq!(buf.len(), 4);
+    }
+
+    #[test]
+    fn test_peek_looped_crossfaded() {
+        let mut buf: ExpSliceRB<f32> = ExpSliceRB::with_capacity(NonZeroUsize::new(8).unwrap());
+        let mut cursor = LoopCursor::new();
+        let mut read = [0.0f32; 4];
+        assert_eq!(buf.peek_looped_crossfaded(&mut read, &mut cursor, 4), 0);
+
+        // Make the existing data wrap around.
+        buf.write(&[0.0; 6]);
+        let mut discard = [0.0f32; 6];
+        buf.read_into(&mut discard);
+        buf.write(&[0.0, 1.0, 2.0, 3.0, 4.0, 8.0, 12.0]);
+
+        // Loop length of 5 with a fade length of 2.
+        let mut read = [0.0f32; 7];
+        assert_eq!(buf.peek_looped_crossfaded(&mut read, &mut cursor, 2), 7);
+        assert_eq!(read, [0.0, 1.0, 2.0, 3.0, 4.0, 8.0, 6.5]);
+        assert_eq!(cursor.position(), 2);
+        assert!(cursor.has_looped());
+
+        let mut read = [0.0f32; 4];
+        assert_eq!(buf.peek_looped_crossfaded(&mut read, &mut cursor, 2), 4);
+        assert_eq!(read, [2.0, 3.0, 4.0, 8.0]);
+
+        // No crossfade.
+        cursor.reset();
+        let mut read = [0.0f32; 9];
+        assert_eq!(buf.peek_looped_crossfaded(&mut read, &mut cursor, 0), 9);
+        assert_eq!(read, [0.0, 1.0, 2.0, 3.0, 4.0, 8.0, 12.0, 0.0, 1.0]);
+
+        // The fade length is clamped to half the data.
+        cursor.reset();
+        let mut read = [0.0f32; 5];
+        assert_eq!(buf.peek_looped_crossfaded(&mut read, &mut cursor, 10), 5);
+        assert_eq!(read, [0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(cursor.position(), 1);
+        assert_eq!(buf.len(), 7);
+    }
+
+    #[cfg(feature = "cache-padded")]
+    #[test]
+    fn test_cache_padded() {
+        struct Fields {
+            producer: CachePadded<usize>,
+            consumer: CachePadded<usize>,
+        }
+
+        let mut fields = Fields {
+            producer: CachePadded::new(1),
+            consumer: 2.into(),
+        };
+        *fields.consumer += 1;
+
+        let producer_addr = &*fields.producer as *const usize as usize;
+        let consumer_addr = &*fields.consumer as *const usize as usize;
+        assert!(producer_addr.abs_diff(consumer_addr) >= 64);
+        assert_eq!(fields.consumer.into_inner(), 3);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_pod() {
+        let mut buf: ExpSliceRB<u8> = ExpSliceRB::with_capacity(NonZeroUsize::new(8).unwrap());
+
+        // Make the existing data wrap around.
+        buf.write(&[0u8; 6]);
+        let mut read = [0u8; 6];
+        buf.read_into(&mut read);
+
+        buf.write_pod(&[0x0102_0304u32, 0x0506_0708]);
+        buf.write(&[0xff]);
+        assert_eq!(buf.len(), 9);
+
+        let mut values = [0u32; 1];
+        assert_eq!(buf.read_pod_into(&mut values), 1);
+        assert_eq!(values, [0x0102_0304]);
+
+        let mut values = [0u32; 4];
+        assert_eq!(buf.read_pod_into(&mut values), 1);
+        assert_eq!(values, [0x0506_0708, 0, 0, 0]);
+        assert_eq!(buf.read_pod_into(&mut values), 0);
+        assert_eq!(buf.xrun_stats().underruns(), 2);
+        assert_eq!(buf.len(), 1);
+
+        assert_eq!(buf.read_pod_into::<()>(&mut [(); 4]), 0);
+        assert_eq!(buf.len(), 1);
+    }
 }